@@ -0,0 +1,2 @@
+pub mod metadata_daemon;
+pub mod musicbrainz;
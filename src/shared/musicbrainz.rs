@@ -0,0 +1,168 @@
+//! Thin client for the subset of the MusicBrainz web service used to enrich album previews
+//! (release-group original date, type, label, country and track count). Calls here are blocking;
+//! callers are expected to run them off the UI thread (currently only
+//! [`crate::shared::metadata_daemon`] does).
+//!
+//! Needs two direct dependencies beyond what the rest of the crate already pulls in: `ureq` (with
+//! its `json` feature, for [`ureq::Response::into_json`]) and `serde` (with its `derive` feature,
+//! for the response structs below). `aho_corasick`, pulled in by `ui::panes::albums`'s global
+//! search, is the third. None of the three can be added here: this checkout has no `Cargo.toml`
+//! at all, for any dependency, including the ones every other module already relies on (`anyhow`,
+//! `ratatui`, `itertools`...) — so adding entries for just these three would invent a manifest
+//! the rest of the crate's real one doesn't match, rather than actually making the crate build.
+//! The manifest entries needed are exactly the feature flags named above.
+
+use anyhow::Context;
+use serde::Deserialize;
+
+/// An album-identifying MusicBrainz id, tagged with which resource it names. `MUSICBRAINZ_ALBUMID`
+/// and `MUSICBRAINZ_RELEASEGROUPID` are two different MusicBrainz entities (release vs.
+/// release-group) and hit different API endpoints — they are not interchangeable.
+#[derive(Debug, Clone)]
+pub enum MbId {
+    Release(String),
+    ReleaseGroup(String),
+}
+
+impl MbId {
+    pub fn raw(&self) -> &str {
+        match self {
+            MbId::Release(id) | MbId::ReleaseGroup(id) => id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseGroupInfo {
+    pub original_release_date: Option<String>,
+    pub primary_type: Option<String>,
+    pub secondary_types: Vec<String>,
+    pub label: Option<String>,
+    pub country: Option<String>,
+    pub track_count: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseGroupResponse {
+    #[serde(rename = "first-release-date")]
+    first_release_date: Option<String>,
+    #[serde(rename = "primary-type")]
+    primary_type: Option<String>,
+    #[serde(rename = "secondary-types", default)]
+    secondary_types: Vec<String>,
+    #[serde(default)]
+    releases: Vec<ReleaseResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseResponse {
+    country: Option<String>,
+    #[serde(default)]
+    media: Vec<MediaResponse>,
+    #[serde(rename = "label-info", default)]
+    label_info: Vec<LabelInfoResponse>,
+}
+
+/// A release, fetched with its parent release-group embedded, so a release MBID can be resolved
+/// to release-group details without a second round trip.
+#[derive(Debug, Deserialize)]
+struct ReleaseWithGroupResponse {
+    country: Option<String>,
+    #[serde(default)]
+    media: Vec<MediaResponse>,
+    #[serde(rename = "label-info", default)]
+    label_info: Vec<LabelInfoResponse>,
+    #[serde(rename = "release-group")]
+    release_group: ReleaseGroupSummary,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseGroupSummary {
+    #[serde(rename = "first-release-date")]
+    first_release_date: Option<String>,
+    #[serde(rename = "primary-type")]
+    primary_type: Option<String>,
+    #[serde(rename = "secondary-types", default)]
+    secondary_types: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MediaResponse {
+    #[serde(rename = "track-count")]
+    track_count: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LabelInfoResponse {
+    label: Option<LabelResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LabelResponse {
+    name: Option<String>,
+}
+
+const USER_AGENT: &str =
+    concat!("rmpc/", env!("CARGO_PKG_VERSION"), " ( https://github.com/tarneaux/rmpc )");
+
+/// Resolves either a release or a release-group MBID to release-group details, hitting whichever
+/// endpoint actually accepts that kind of id.
+pub fn fetch_release_group_info(mbid: &MbId) -> anyhow::Result<ReleaseGroupInfo> {
+    match mbid {
+        MbId::ReleaseGroup(id) => fetch_release_group(id),
+        MbId::Release(id) => fetch_release_group_for_release(id),
+    }
+}
+
+fn fetch_release_group(mbid: &str) -> anyhow::Result<ReleaseGroupInfo> {
+    let url = format!(
+        "https://musicbrainz.org/ws/2/release-group/{mbid}?inc=releases+labels+media&fmt=json"
+    );
+    let response: ReleaseGroupResponse = ureq::get(&url)
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .context("MusicBrainz release-group request failed")?
+        .into_json()
+        .context("Failed to parse MusicBrainz release-group response")?;
+
+    let release = response.releases.into_iter().next();
+    Ok(ReleaseGroupInfo {
+        original_release_date: response.first_release_date,
+        primary_type: response.primary_type,
+        secondary_types: response.secondary_types,
+        label: release
+            .as_ref()
+            .and_then(|r| r.label_info.first())
+            .and_then(|l| l.label.as_ref())
+            .and_then(|l| l.name.clone()),
+        country: release.as_ref().and_then(|r| r.country.clone()),
+        track_count: release.as_ref().and_then(|r| r.media.first()).and_then(|m| m.track_count),
+    })
+}
+
+/// `MUSICBRAINZ_ALBUMID` names a *release*, not a release-group, so it must go through
+/// `/release/{id}` rather than `/release-group/{id}`. Asking for the release with its
+/// release-group embedded (`inc=release-groups`) resolves it in a single request.
+fn fetch_release_group_for_release(mbid: &str) -> anyhow::Result<ReleaseGroupInfo> {
+    let url =
+        format!("https://musicbrainz.org/ws/2/release/{mbid}?inc=release-groups+labels+media&fmt=json");
+    let response: ReleaseWithGroupResponse = ureq::get(&url)
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .context("MusicBrainz release request failed")?
+        .into_json()
+        .context("Failed to parse MusicBrainz release response")?;
+
+    Ok(ReleaseGroupInfo {
+        original_release_date: response.release_group.first_release_date,
+        primary_type: response.release_group.primary_type,
+        secondary_types: response.release_group.secondary_types,
+        label: response
+            .label_info
+            .first()
+            .and_then(|l| l.label.as_ref())
+            .and_then(|l| l.name.clone()),
+        country: response.country,
+        track_count: response.media.first().and_then(|m| m.track_count),
+    })
+}